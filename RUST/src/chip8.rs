@@ -1,6 +1,6 @@
 use rand::Rng; // Nécessaire pour l'instruction CXNN (aléatoire)
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Write};
 
 // Le set de police (inchangé par rapport au C++)
 const FONTSET: [u8; 80] = [
@@ -22,6 +22,85 @@ const FONTSET: [u8; 80] = [
     0xF0, 0x80, 0xF0, 0x80, 0x80  // F
 ];
 
+// Police SCHIP 8x10, pour les gros chiffres hexadécimaux (opcode FX30).
+// Chargée en mémoire juste après FONTSET (0x050 à 0x0F0).
+const BIGFONT: [u8; 160] = [
+    0x3C, 0x7E, 0xE7, 0xC3, 0xC3, 0xC3, 0xC3, 0xE7, 0x7E, 0x3C, // 0
+    0x18, 0x38, 0x58, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x3C, // 1
+    0x3E, 0x7F, 0xC3, 0x06, 0x0C, 0x18, 0x30, 0x60, 0xFF, 0xFF, // 2
+    0x3C, 0x7E, 0xC3, 0x03, 0x0E, 0x0E, 0x03, 0xC3, 0x7E, 0x3C, // 3
+    0x06, 0x0E, 0x1E, 0x36, 0x66, 0xC6, 0xFF, 0xFF, 0x06, 0x06, // 4
+    0xFF, 0xFF, 0xC0, 0xC0, 0xFC, 0xFE, 0x03, 0xC3, 0x7E, 0x3C, // 5
+    0x3E, 0x7C, 0xC0, 0xC0, 0xFC, 0xFE, 0xC3, 0xC3, 0x7E, 0x3C, // 6
+    0xFF, 0xFF, 0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x60, 0x60, // 7
+    0x3C, 0x7E, 0xC3, 0xC3, 0x7E, 0x7E, 0xC3, 0xC3, 0x7E, 0x3C, // 8
+    0x3C, 0x7E, 0xC3, 0xC3, 0x7F, 0x3F, 0x03, 0x03, 0x3E, 0x7C, // 9
+    0x18, 0x3C, 0x66, 0xC3, 0xC3, 0xFF, 0xFF, 0xC3, 0xC3, 0xC3, // A
+    0xFC, 0xFE, 0xC3, 0xC3, 0xFC, 0xFE, 0xC3, 0xC3, 0xFE, 0xFC, // B
+    0x3C, 0x7E, 0xC3, 0xC0, 0xC0, 0xC0, 0xC0, 0xC3, 0x7E, 0x3C, // C
+    0xFC, 0xFE, 0xC3, 0xC3, 0xC3, 0xC3, 0xC3, 0xC3, 0xFE, 0xFC, // D
+    0xFF, 0xFF, 0xC0, 0xC0, 0xFC, 0xFC, 0xC0, 0xC0, 0xFF, 0xFF, // E
+    0xFF, 0xFF, 0xC0, 0xC0, 0xFC, 0xFC, 0xC0, 0xC0, 0xC0, 0xC0, // F
+];
+
+// Réglages des comportements ambigus du CHIP-8 : les ROMs ont été écrites
+// pour des interpréteurs différents (COSMAC VIP, SCHIP, ...) qui ne sont
+// pas d'accord entre eux sur certains opcodes. On rend ces divergences
+// configurables plutôt que de figer un seul comportement.
+#[derive(Debug, Clone, Copy)]
+pub struct Quirks {
+    // 8XY1/8XY2/8XY3 (OR/AND/XOR) remettent aussi V[0xF] à zéro sur le VIP.
+    pub vf_reset: bool,
+    // FX55/FX65 laissent I inchangé sur SCHIP au lieu de l'incrémenter de x+1.
+    pub memory_increment_by_x: bool,
+    // 8XY6/8XYE décalent V[y] (VIP) plutôt que V[x] en place (SCHIP).
+    pub shift_uses_vy: bool,
+    // BNNN saute à NNN + V[x] (SCHIP) plutôt que NNN + V[0] (VIP).
+    pub jump_uses_vx: bool,
+    // DXYN découpe les sprites aux bords de l'écran au lieu de les faire
+    // réapparaître de l'autre côté (wrap).
+    pub clip_sprites: bool,
+}
+
+impl Quirks {
+    // Comportement du COSMAC VIP original.
+    pub fn cosmac_vip() -> Self {
+        Quirks {
+            vf_reset: true,
+            memory_increment_by_x: true,
+            shift_uses_vy: true,
+            jump_uses_vx: false,
+            clip_sprites: true,
+        }
+    }
+
+    // Comportement du SUPER-CHIP.
+    pub fn schip() -> Self {
+        Quirks {
+            vf_reset: false,
+            memory_increment_by_x: false,
+            shift_uses_vy: false,
+            jump_uses_vx: true,
+            clip_sprites: true,
+        }
+    }
+}
+
+impl Default for Quirks {
+    // Le set historique de ce portage : celui qui était codé en dur avant
+    // l'introduction de cette struct, sauf pour DXYN, où les sprites qui
+    // dépassent réapparaissent de l'autre côté (wrap).
+    fn default() -> Self {
+        Quirks {
+            vf_reset: false,
+            memory_increment_by_x: true,
+            shift_uses_vy: false,
+            jump_uses_vx: false,
+            clip_sprites: false,
+        }
+    }
+}
+
 pub struct Chip8 {
     // Composants internes (privés comme en C++)
     opcode: u16,
@@ -29,24 +108,47 @@ pub struct Chip8 {
     v: [u8; 16],      // Registres V0-VF
     i: u16,           // Index register
     pc: u16,          // Program counter
-    
+
     stack: [u16; 16],
     sp: u16,          // Stack pointer
 
     pub delay_timer: u8,
     pub sound_timer: u8,
 
+    quirks: Quirks,
+
+    // Nombre d'instructions exécutées par frame à 60 Hz (voir `tick_timers` :
+    // les timers ne dépendent plus de la vitesse d'exécution des opcodes).
+    pub cycles_per_frame: u32,
+
+    // RPL user flags (FX75/FX85), persistés indépendamment des registres V.
+    rpl: [u8; 8],
+
+    // Mode SUPER-CHIP : écran 128x64 au lieu de 64x32. `gfx` est redimensionné
+    // en conséquence à chaque bascule (voir `set_hires`).
+    pub hires: bool,
+
     // Composants publics (accessibles par le main)
     // En Rust, on préfère souvent des getters, mais pour rester proche
     // de ton code C++ (gfx public), on les met 'pub'.
-    pub gfx: [u8; 64 * 32],
+    // `gfx` était un tableau fixe 64x32 ; en SCHIP la résolution peut
+    // changer à l'exécution, donc c'est maintenant un Vec dimensionné
+    // dynamiquement (voir `width`/`height`).
+    pub gfx: Vec<u8>,
     pub key: [u8; 16],
     pub draw_flag: bool,
+    // Mis à true par l'opcode 00FD (exit), que `main` consulte pour quitter.
+    pub should_exit: bool,
 }
 
 impl Chip8 {
     // Constructeur : remplace Chip8() et init()
     pub fn new() -> Self {
+        Self::new_with_quirks(Quirks::default())
+    }
+
+    // Même constructeur, mais avec un jeu de quirks explicite (voir `Quirks`).
+    pub fn new_with_quirks(quirks: Quirks) -> Self {
         let mut c = Chip8 {
             opcode: 0,
             memory: [0; 4096],
@@ -57,9 +159,14 @@ impl Chip8 {
             sp: 0,
             delay_timer: 0,
             sound_timer: 0,
-            gfx: [0; 64 * 32],
+            quirks,
+            cycles_per_frame: 10,
+            rpl: [0; 8],
+            hires: false,
+            gfx: vec![0; 64 * 32],
             key: [0; 16],
             draw_flag: false,
+            should_exit: false,
         };
 
         // Charger la police en mémoire (0x000 à 0x050)
@@ -67,9 +174,139 @@ impl Chip8 {
             c.memory[i] = FONTSET[i];
         }
 
+        // Charger la grosse police SCHIP juste après (0x050 à 0x0F0)
+        for (i, &byte) in BIGFONT.iter().enumerate() {
+            c.memory[80 + i] = byte;
+        }
+
         c
     }
 
+    // Largeur courante du plan graphique (64 en mode normal, 128 en hires).
+    pub fn width(&self) -> usize {
+        if self.hires { 128 } else { 64 }
+    }
+
+    // Hauteur courante du plan graphique (32 en mode normal, 64 en hires).
+    pub fn height(&self) -> usize {
+        if self.hires { 64 } else { 32 }
+    }
+
+    // Vrai tant que le buzzer doit sonner (voir l'opcode FX18).
+    pub fn is_beeping(&self) -> bool {
+        self.sound_timer > 0
+    }
+
+    // Bascule la résolution et redimensionne `gfx` en conséquence,
+    // en effaçant l'écran (comme le ferait un vrai changement de mode SCHIP).
+    fn set_hires(&mut self, hires: bool) {
+        self.hires = hires;
+        self.gfx = vec![0; self.width() * self.height()];
+        self.draw_flag = true;
+    }
+
+    // 00CN : fait descendre le contenu de l'écran de `n` lignes.
+    fn scroll_down(&mut self, n: usize) {
+        let w = self.width();
+        let h = self.height();
+        for y in (0..h).rev() {
+            for x in 0..w {
+                self.gfx[y * w + x] = if y >= n { self.gfx[(y - n) * w + x] } else { 0 };
+            }
+        }
+    }
+
+    // 00FC : décale le contenu de l'écran de 4 pixels vers la gauche.
+    fn scroll_left(&mut self, n: usize) {
+        let w = self.width();
+        let h = self.height();
+        for y in 0..h {
+            for x in 0..w {
+                self.gfx[y * w + x] = if x + n < w { self.gfx[y * w + x + n] } else { 0 };
+            }
+        }
+    }
+
+    // 00FB : décale le contenu de l'écran de 4 pixels vers la droite.
+    fn scroll_right(&mut self, n: usize) {
+        let w = self.width();
+        let h = self.height();
+        for y in 0..h {
+            for x in (0..w).rev() {
+                self.gfx[y * w + x] = if x >= n { self.gfx[y * w + x - n] } else { 0 };
+            }
+        }
+    }
+
+    // --- Inspection en lecture seule, pour le débogueur (voir `debugger.rs`) ---
+
+    pub fn registers(&self) -> &[u8; 16] {
+        &self.v
+    }
+
+    pub fn index_register(&self) -> u16 {
+        self.i
+    }
+
+    pub fn pc(&self) -> u16 {
+        self.pc
+    }
+
+    pub fn stack_pointer(&self) -> u16 {
+        self.sp
+    }
+
+    pub fn stack(&self) -> &[u16; 16] {
+        &self.stack
+    }
+
+    // Exécute un cycle et renvoie (pc avant exécution, opcode exécuté), pour
+    // que l'appelant puisse l'enregistrer (historique du débogueur) sans
+    // avoir accès à la mémoire ou au PC internes.
+    pub fn step(&mut self) -> (u16, u16) {
+        let pc_before = self.pc;
+        self.emulate_cycle();
+        (pc_before, self.opcode)
+    }
+
+    // Capture l'état complet de la machine (quick-save), indépendamment du
+    // chemin F1 qui reconstruit un `Chip8` neuf et recharge la ROM.
+    pub fn snapshot(&self) -> Chip8State {
+        Chip8State {
+            memory: self.memory,
+            v: self.v,
+            i: self.i,
+            pc: self.pc,
+            stack: self.stack,
+            sp: self.sp,
+            delay_timer: self.delay_timer,
+            sound_timer: self.sound_timer,
+            hires: self.hires,
+            gfx: self.gfx.clone(),
+            key: self.key,
+            rpl: self.rpl,
+        }
+    }
+
+    // Restaure un état capturé par `snapshot` (quick-load) : remet la
+    // machine exactement là où elle en était, framebuffer compris, et
+    // redemande un rendu.
+    pub fn restore(&mut self, state: &Chip8State) {
+        self.memory = state.memory;
+        self.v = state.v;
+        self.i = state.i;
+        self.pc = state.pc;
+        self.stack = state.stack;
+        self.sp = state.sp;
+        self.delay_timer = state.delay_timer;
+        self.sound_timer = state.sound_timer;
+        self.hires = state.hires;
+        self.gfx = state.gfx.clone();
+        self.key = state.key;
+        self.rpl = state.rpl;
+        self.draw_flag = true;
+    }
+
     // Chargement de la ROM
     // Différence majeure : On retourne un Result pour gérer les erreurs proprement
     // au lieu de return true/false et fprintf.
@@ -108,18 +345,51 @@ impl Chip8 {
         // On utilise 'match' sur le quartet de poids fort (ex: 0xA2F0 & 0xF000 => 0xA000)
         match self.opcode & 0xF000 {
             0x0000 => {
-                match self.opcode & 0x000F {
-                    0x0000 => { // 00E0: Clear screen
-                        self.gfx = [0; 64 * 32];
+                match self.opcode & 0x00FF {
+                    0x00E0 => { // 00E0: Clear screen
+                        let len = self.gfx.len();
+                        self.gfx = vec![0; len];
                         self.draw_flag = true;
                         self.pc += 2;
                     },
-                    0x000E => { // 00EE: Return from subroutine
+                    0x00EE => { // 00EE: Return from subroutine
                         self.sp -= 1;
                         self.pc = self.stack[self.sp as usize];
                         self.pc += 2;
                     },
-                    _ => panic!("Unknown opcode [0x0000]: {:X}", self.opcode),
+                    0x00FB => { // 00FB: Scroll right 4px (SCHIP)
+                        self.scroll_right(4);
+                        self.draw_flag = true;
+                        self.pc += 2;
+                    },
+                    0x00FC => { // 00FC: Scroll left 4px (SCHIP)
+                        self.scroll_left(4);
+                        self.draw_flag = true;
+                        self.pc += 2;
+                    },
+                    0x00FD => { // 00FD: Exit interpreter (SCHIP)
+                        self.should_exit = true;
+                        self.pc += 2;
+                    },
+                    0x00FE => { // 00FE: Disable hires (SCHIP)
+                        self.set_hires(false);
+                        self.pc += 2;
+                    },
+                    0x00FF => { // 00FF: Enable hires (SCHIP)
+                        self.set_hires(true);
+                        self.pc += 2;
+                    },
+                    _ => {
+                        // 00CN: Scroll down N rows (SCHIP) ; seul cas restant du groupe 0x00C_.
+                        if self.opcode & 0x00F0 == 0x00C0 {
+                            let n = (self.opcode & 0x000F) as usize;
+                            self.scroll_down(n);
+                            self.draw_flag = true;
+                            self.pc += 2;
+                        } else {
+                            panic!("Unknown opcode [0x0000]: {:X}", self.opcode);
+                        }
+                    },
                 }
             },
             0x1000 => { // 1NNN: Jump
@@ -175,9 +445,21 @@ impl Chip8 {
                 
                 match self.opcode & 0x000F {
                     0x0000 => { self.v[x] = self.v[y]; self.pc += 2; },
-                    0x0001 => { self.v[x] |= self.v[y]; self.pc += 2; },
-                    0x0002 => { self.v[x] &= self.v[y]; self.pc += 2; },
-                    0x0003 => { self.v[x] ^= self.v[y]; self.pc += 2; },
+                    0x0001 => {
+                        self.v[x] |= self.v[y];
+                        if self.quirks.vf_reset { self.v[0xF] = 0; }
+                        self.pc += 2;
+                    },
+                    0x0002 => {
+                        self.v[x] &= self.v[y];
+                        if self.quirks.vf_reset { self.v[0xF] = 0; }
+                        self.pc += 2;
+                    },
+                    0x0003 => {
+                        self.v[x] ^= self.v[y];
+                        if self.quirks.vf_reset { self.v[0xF] = 0; }
+                        self.pc += 2;
+                    },
                     0x0004 => { // Add with carry
                         let (res, overflow) = self.v[x].overflowing_add(self.v[y]);
                         self.v[0xF] = if overflow { 1 } else { 0 };
@@ -195,8 +477,9 @@ impl Chip8 {
                         self.pc += 2;
                     },
                     0x0006 => { // Shift Right
-                        self.v[0xF] = self.v[x] & 0x1;
-                        self.v[x] >>= 1;
+                        let src = if self.quirks.shift_uses_vy { self.v[y] } else { self.v[x] };
+                        self.v[0xF] = src & 0x1;
+                        self.v[x] = src >> 1;
                         self.pc += 2;
                     },
                     0x0007 => { // SubN (VY - VX)
@@ -206,8 +489,9 @@ impl Chip8 {
                         self.pc += 2;
                     },
                     0x000E => { // Shift Left
-                        self.v[0xF] = (self.v[x] >> 7) & 1;
-                        self.v[x] <<= 1;
+                        let src = if self.quirks.shift_uses_vy { self.v[y] } else { self.v[x] };
+                        self.v[0xF] = (src >> 7) & 1;
+                        self.v[x] = src << 1;
                         self.pc += 2;
                     },
                     _ => panic!("Unknown opcode [0x8000]: {:X}", self.opcode),
@@ -226,9 +510,14 @@ impl Chip8 {
                 self.i = self.opcode & 0x0FFF;
                 self.pc += 2;
             },
-            0xB000 => { // BNNN: Jump to NNN + V0
+            0xB000 => { // BNNN: Jump to NNN + V0 (ou BXNN: NNN + VX, voir quirks.jump_uses_vx)
                 let nnn = self.opcode & 0x0FFF;
-                self.pc = nnn + (self.v[0] as u16);
+                let reg = if self.quirks.jump_uses_vx {
+                    ((self.opcode & 0x0F00) >> 8) as usize
+                } else {
+                    0
+                };
+                self.pc = nnn.wrapping_add(self.v[reg] as u16);
             },
             0xC000 => { // CXNN: Random
                 let x = ((self.opcode & 0x0F00) >> 8) as usize;
@@ -237,28 +526,60 @@ impl Chip8 {
                 self.v[x] = rng & nn;
                 self.pc += 2;
             },
-            0xD000 => { // DXYN: Draw
+            0xD000 => { // DXYN: Draw (N=0 => sprite 16x16, voir SCHIP)
                 let x = self.v[((self.opcode & 0x0F00) >> 8) as usize] as u16;
                 let y = self.v[((self.opcode & 0x00F0) >> 4) as usize] as u16;
-                let height = self.opcode & 0x000F;
-                
+                let n = self.opcode & 0x000F;
+                let (height, sprite_width) = if n == 0 { (16, 16) } else { (n, 8) };
+                let bytes_per_row = sprite_width / 8;
+
+                let w = self.width() as u16;
+                let h = self.height() as u16;
+
                 self.v[0xF] = 0;
-                
+                let mut cleared_rows: u16 = 0;
+
                 for yline in 0..height {
-                    let pixel = self.memory[(self.i + yline) as usize];
-                    for xline in 0..8 {
-                        if (pixel & (0x80 >> xline)) != 0 {
-                            let idx = (x + xline + ((y + yline) * 64)) as usize;
-                            // Sécurité: on évite de sortir du tableau gfx
-                            if idx < self.gfx.len() {
+                    let py = y + yline;
+                    if self.quirks.clip_sprites && py >= h {
+                        continue;
+                    }
+                    let py = py % h;
+                    let mut row_collided = false;
+
+                    for byte_idx in 0..bytes_per_row {
+                        let pixel = self.memory[(self.i + yline * bytes_per_row + byte_idx) as usize];
+                        for bit in 0..8 {
+                            if (pixel & (0x80 >> bit)) != 0 {
+                                let px = x + byte_idx * 8 + bit;
+                                if self.quirks.clip_sprites && px >= w {
+                                    continue;
+                                }
+                                let px = px % w;
+                                let idx = (px + py * w) as usize;
                                 if self.gfx[idx] == 1 {
-                                    self.v[0xF] = 1;
+                                    row_collided = true;
                                 }
                                 self.gfx[idx] ^= 1;
                             }
                         }
                     }
+
+                    if row_collided {
+                        cleared_rows += 1;
+                    }
                 }
+
+                // En hires SCHIP, VF compte le nombre de lignes qui ont eu
+                // une collision ; en lores, c'est un simple booléen 0/1.
+                self.v[0xF] = if self.hires {
+                    cleared_rows.min(255) as u8
+                } else if cleared_rows > 0 {
+                    1
+                } else {
+                    0
+                };
+
                 self.draw_flag = true;
                 self.pc += 2;
             },
@@ -314,6 +635,10 @@ impl Chip8 {
                         self.i = (self.v[x] as u16) * 5;
                         self.pc += 2;
                     },
+                    0x0030 => { // FX30: Big font char (SCHIP), 8x10
+                        self.i = 80 + (self.v[x] as u16) * 10;
+                        self.pc += 2;
+                    },
                     0x0033 => { // BCD
                         self.memory[self.i as usize] = self.v[x] / 100;
                         self.memory[(self.i + 1) as usize] = (self.v[x] / 10) % 10;
@@ -324,14 +649,30 @@ impl Chip8 {
                         for i in 0..=x {
                             self.memory[(self.i as usize) + i] = self.v[i];
                         }
-                        self.i += (x as u16) + 1;
+                        if self.quirks.memory_increment_by_x {
+                            self.i += (x as u16) + 1;
+                        }
                         self.pc += 2;
                     },
                     0x0065 => { // Load Regs
                         for i in 0..=x {
                             self.v[i] = self.memory[(self.i as usize) + i];
                         }
-                        self.i += (x as u16) + 1;
+                        if self.quirks.memory_increment_by_x {
+                            self.i += (x as u16) + 1;
+                        }
+                        self.pc += 2;
+                    },
+                    0x0075 => { // FX75: Save V0..VX to RPL flags (SCHIP)
+                        for i in 0..=x.min(7) {
+                            self.rpl[i] = self.v[i];
+                        }
+                        self.pc += 2;
+                    },
+                    0x0085 => { // FX85: Restore V0..VX from RPL flags (SCHIP)
+                        for i in 0..=x.min(7) {
+                            self.v[i] = self.rpl[i];
+                        }
                         self.pc += 2;
                     },
                     _ => panic!("Unknown opcode [0xF000]: {:X}", self.opcode),
@@ -339,16 +680,274 @@ impl Chip8 {
             },
             _ => panic!("Unknown opcode: {:X}", self.opcode),
         }
+    }
 
-        // Timers
+    // Décrémente les timers. À appeler exactement une fois par 1/60s, depuis
+    // la boucle de frame de `main`, et non plus à chaque `emulate_cycle` :
+    // la vitesse des timers ne doit pas dépendre de la vitesse d'exécution
+    // des opcodes.
+    pub fn tick_timers(&mut self) {
         if self.delay_timer > 0 {
             self.delay_timer -= 1;
         }
         if self.sound_timer > 0 {
-            if self.sound_timer == 1 {
-                // Sound logic (not implemented in original)
-            }
             self.sound_timer -= 1;
         }
     }
 }
+
+// Capture complète de l'état d'une machine Chip8, produite par
+// `Chip8::snapshot` et appliquée par `Chip8::restore` (quick-save/quick-load,
+// voir F5/F9 dans `main`).
+pub struct Chip8State {
+    memory: [u8; 4096],
+    v: [u8; 16],
+    i: u16,
+    pc: u16,
+    stack: [u16; 16],
+    sp: u16,
+    delay_timer: u8,
+    sound_timer: u8,
+    hires: bool,
+    gfx: Vec<u8>,
+    key: [u8; 16],
+    rpl: [u8; 8],
+}
+
+// Lit `len` octets à partir de `*offset` dans `buffer`, et avance `*offset`.
+// Renvoie une erreur (plutôt que de paniquer) si le buffer est trop court,
+// ce qui arrive pour un fichier de sauvegarde tronqué ou corrompu.
+fn take<'a>(buffer: &'a [u8], offset: &mut usize, len: usize) -> std::io::Result<&'a [u8]> {
+    if *offset + len > buffer.len() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "corrupted save state: unexpected end of file",
+        ));
+    }
+    let slice = &buffer[*offset..*offset + len];
+    *offset += len;
+    Ok(slice)
+}
+
+impl Chip8State {
+    // Sérialise l'état dans un format binaire compact (pas de dépendance
+    // externe) : champs de taille fixe suivis du plan graphique préfixé
+    // par sa longueur, puisque sa taille dépend du mode hires.
+    pub fn write_to_file(&self, file_path: &str) -> std::io::Result<()> {
+        let mut file = File::create(file_path)?;
+
+        file.write_all(&self.memory)?;
+        file.write_all(&self.v)?;
+        file.write_all(&self.i.to_le_bytes())?;
+        file.write_all(&self.pc.to_le_bytes())?;
+        for slot in &self.stack {
+            file.write_all(&slot.to_le_bytes())?;
+        }
+        file.write_all(&self.sp.to_le_bytes())?;
+        file.write_all(&[self.delay_timer, self.sound_timer, self.hires as u8])?;
+        file.write_all(&(self.gfx.len() as u32).to_le_bytes())?;
+        file.write_all(&self.gfx)?;
+        file.write_all(&self.key)?;
+        file.write_all(&self.rpl)?;
+
+        Ok(())
+    }
+
+    // Relit un état écrit par `write_to_file`. Renvoie une erreur (au lieu de
+    // paniquer) si le fichier est tronqué ou corrompu, pour que l'appelant
+    // puisse l'afficher proprement plutôt que de planter toute la session.
+    pub fn read_from_file(file_path: &str) -> std::io::Result<Chip8State> {
+        let mut file = File::open(file_path)?;
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+
+        let mut offset = 0usize;
+
+        let mut memory = [0u8; 4096];
+        memory.copy_from_slice(take(&buffer, &mut offset, 4096)?);
+
+        let mut v = [0u8; 16];
+        v.copy_from_slice(take(&buffer, &mut offset, 16)?);
+
+        let i = u16::from_le_bytes(take(&buffer, &mut offset, 2)?.try_into().unwrap());
+        let pc = u16::from_le_bytes(take(&buffer, &mut offset, 2)?.try_into().unwrap());
+
+        let mut stack = [0u16; 16];
+        for slot in &mut stack {
+            *slot = u16::from_le_bytes(take(&buffer, &mut offset, 2)?.try_into().unwrap());
+        }
+
+        let sp = u16::from_le_bytes(take(&buffer, &mut offset, 2)?.try_into().unwrap());
+        let timers_and_hires = take(&buffer, &mut offset, 3)?;
+        let delay_timer = timers_and_hires[0];
+        let sound_timer = timers_and_hires[1];
+        let hires = timers_and_hires[2] != 0;
+
+        let gfx_len = u32::from_le_bytes(take(&buffer, &mut offset, 4)?.try_into().unwrap()) as usize;
+
+        // `gfx_len` doit correspondre à la résolution annoncée par `hires` :
+        // sinon `restore` installerait un plan graphique trop court, qui
+        // panique au premier rendu (indexation par width*height en hires).
+        let expected_gfx_len = if hires { 128 * 64 } else { 64 * 32 };
+        if gfx_len != expected_gfx_len {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "corrupted save state: gfx length {} does not match hires={} (expected {})",
+                    gfx_len, hires, expected_gfx_len
+                ),
+            ));
+        }
+
+        let gfx = take(&buffer, &mut offset, gfx_len)?.to_vec();
+
+        let mut key = [0u8; 16];
+        key.copy_from_slice(take(&buffer, &mut offset, 16)?);
+
+        let mut rpl = [0u8; 8];
+        rpl.copy_from_slice(take(&buffer, &mut offset, 8)?);
+
+        Ok(Chip8State {
+            memory,
+            v,
+            i,
+            pc,
+            stack,
+            sp,
+            delay_timer,
+            sound_timer,
+            hires,
+            gfx,
+            key,
+            rpl,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        format!("{}/chip8_test_{}", std::env::temp_dir().display(), name)
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_file() {
+        let mut c = Chip8::new_with_quirks(Quirks::schip());
+        c.v[0] = 0x42;
+        c.rpl[3] = 0x7;
+        let path = temp_path("round_trip.chip8");
+        c.snapshot().write_to_file(&path).unwrap();
+
+        let restored = Chip8State::read_from_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(restored.v[0], 0x42);
+        assert_eq!(restored.rpl[3], 0x7);
+        assert!(!restored.hires);
+        assert_eq!(restored.gfx.len(), 64 * 32);
+    }
+
+    #[test]
+    fn read_from_file_rejects_truncated_save() {
+        let c = Chip8::new();
+        let path = temp_path("truncated.chip8");
+        c.snapshot().write_to_file(&path).unwrap();
+
+        // Tronque le fichier au milieu du champ memory.
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes.truncate(10);
+        std::fs::write(&path, &bytes).unwrap();
+
+        let result = Chip8State::read_from_file(&path);
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn read_from_file_rejects_gfx_len_mismatch_with_hires() {
+        // Fichier de sauvegarde corrompu : hires=true mais un gfx de taille
+        // lores, ce qui paniquerait au rendu si on le restaurait tel quel.
+        let state = Chip8State {
+            memory: [0; 4096],
+            v: [0; 16],
+            i: 0,
+            pc: 0x200,
+            stack: [0; 16],
+            sp: 0,
+            delay_timer: 0,
+            sound_timer: 0,
+            hires: true,
+            gfx: vec![0; 64 * 32],
+            key: [0; 16],
+            rpl: [0; 8],
+        };
+        let path = temp_path("hires_mismatch.chip8");
+        state.write_to_file(&path).unwrap();
+
+        let result = Chip8State::read_from_file(&path);
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.is_err());
+    }
+
+    // Écrit un opcode à l'adresse courante du PC et exécute un cycle, pour
+    // tester les branches d'opcodes sensibles aux quirks sans charger de ROM.
+    fn exec_opcode(c: &mut Chip8, opcode: u16) {
+        c.memory[c.pc as usize] = (opcode >> 8) as u8;
+        c.memory[c.pc as usize + 1] = (opcode & 0xFF) as u8;
+        c.emulate_cycle();
+    }
+
+    #[test]
+    fn shift_quirk_chooses_source_register() {
+        // 8XY6 (SHR), x=2, y=1 : le VIP décale V[y], le SCHIP décale V[x].
+        let mut vip = Chip8::new_with_quirks(Quirks::cosmac_vip());
+        vip.v[1] = 0x10;
+        vip.v[2] = 0xFF;
+        exec_opcode(&mut vip, 0x8216);
+        assert_eq!(vip.v[2], 0x10 >> 1);
+
+        let mut schip = Chip8::new_with_quirks(Quirks::schip());
+        schip.v[1] = 0x10;
+        schip.v[2] = 0xFF;
+        exec_opcode(&mut schip, 0x8216);
+        assert_eq!(schip.v[2], 0xFF >> 1);
+    }
+
+    #[test]
+    fn jump_quirk_chooses_offset_register() {
+        // BNNN : le VIP saute à NNN + V0, le SCHIP (BXNN) à NNN + VX.
+        let mut vip = Chip8::new_with_quirks(Quirks::cosmac_vip());
+        vip.v[0] = 0x10;
+        vip.v[3] = 0x20;
+        exec_opcode(&mut vip, 0xB300);
+        assert_eq!(vip.pc(), 0x300 + 0x10);
+
+        let mut schip = Chip8::new_with_quirks(Quirks::schip());
+        schip.v[0] = 0x10;
+        schip.v[3] = 0x20;
+        exec_opcode(&mut schip, 0xB300);
+        assert_eq!(schip.pc(), 0x300 + 0x20);
+    }
+
+    #[test]
+    fn vf_reset_quirk_clears_vf_after_bitwise_ops() {
+        // 8XY1 (OR) : le VIP remet VF à zéro après l'opération, le SCHIP non.
+        let mut vip = Chip8::new_with_quirks(Quirks::cosmac_vip());
+        vip.v[0xF] = 1;
+        vip.v[0] = 0x0F;
+        vip.v[1] = 0xF0;
+        exec_opcode(&mut vip, 0x8011);
+        assert_eq!(vip.v[0xF], 0);
+
+        let mut schip = Chip8::new_with_quirks(Quirks::schip());
+        schip.v[0xF] = 1;
+        schip.v[0] = 0x0F;
+        schip.v[1] = 0xF0;
+        exec_opcode(&mut schip, 0x8011);
+        assert_eq!(schip.v[0xF], 1);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::chip8::Chip8;
+use std::collections::VecDeque;
+
+// Taille de l'historique des instructions exécutées (voir `Debugger::history`).
+const HISTORY_SIZE: usize = 64;
+
+// Découpe un opcode en ses quatre quartets, du plus fort au plus faible
+// (ex: 0xA2F0 -> (0xA, 0x2, 0xF, 0x0)). Utilisé par le désassembleur
+// uniquement : `emulate_cycle` extrait ses champs avec ses propres masques.
+fn get_nibs(opcode: u16) -> (u8, u8, u8, u8) {
+    (
+        ((opcode & 0xF000) >> 12) as u8,
+        ((opcode & 0x0F00) >> 8) as u8,
+        ((opcode & 0x00F0) >> 4) as u8,
+        (opcode & 0x000F) as u8,
+    )
+}
+
+// Désassemble un opcode CHIP-8/SCHIP en mnémonique lisible (ex: "LD I, 0x2F0").
+pub fn disassemble(opcode: u16) -> String {
+    let (n1, x, y, n) = get_nibs(opcode);
+    let nnn = opcode & 0x0FFF;
+    let nn = (opcode & 0x00FF) as u8;
+
+    match n1 {
+        0x0 => match opcode {
+            0x00E0 => "CLS".to_string(),
+            0x00EE => "RET".to_string(),
+            0x00FB => "SCR".to_string(),
+            0x00FC => "SCL".to_string(),
+            0x00FD => "EXIT".to_string(),
+            0x00FE => "LOW".to_string(),
+            0x00FF => "HIGH".to_string(),
+            _ if opcode & 0xFFF0 == 0x00C0 => format!("SCD 0x{:X}", n),
+            _ => format!("SYS 0x{:03X}", nnn),
+        },
+        0x1 => format!("JP 0x{:03X}", nnn),
+        0x2 => format!("CALL 0x{:03X}", nnn),
+        0x3 => format!("SE V{:X}, 0x{:02X}", x, nn),
+        0x4 => format!("SNE V{:X}, 0x{:02X}", x, nn),
+        0x5 => format!("SE V{:X}, V{:X}", x, y),
+        0x6 => format!("LD V{:X}, 0x{:02X}", x, nn),
+        0x7 => format!("ADD V{:X}, 0x{:02X}", x, nn),
+        0x8 => match n {
+            0x0 => format!("LD V{:X}, V{:X}", x, y),
+            0x1 => format!("OR V{:X}, V{:X}", x, y),
+            0x2 => format!("AND V{:X}, V{:X}", x, y),
+            0x3 => format!("XOR V{:X}, V{:X}", x, y),
+            0x4 => format!("ADD V{:X}, V{:X}", x, y),
+            0x5 => format!("SUB V{:X}, V{:X}", x, y),
+            0x6 => format!("SHR V{:X}", x),
+            0x7 => format!("SUBN V{:X}, V{:X}", x, y),
+            0xE => format!("SHL V{:X}", x),
+            _ => format!("DATA 0x{:04X}", opcode),
+        },
+        0x9 => format!("SNE V{:X}, V{:X}", x, y),
+        0xA => format!("LD I, 0x{:03X}", nnn),
+        0xB => format!("JP V0, 0x{:03X}", nnn),
+        0xC => format!("RND V{:X}, 0x{:02X}", x, nn),
+        0xD => format!("DRW V{:X}, V{:X}, {}", x, y, n),
+        0xE => match nn {
+            0x9E => format!("SKP V{:X}", x),
+            0xA1 => format!("SKNP V{:X}", x),
+            _ => format!("DATA 0x{:04X}", opcode),
+        },
+        0xF => match nn {
+            0x07 => format!("LD V{:X}, DT", x),
+            0x0A => format!("LD V{:X}, K", x),
+            0x15 => format!("LD DT, V{:X}", x),
+            0x18 => format!("LD ST, V{:X}", x),
+            0x1E => format!("ADD I, V{:X}", x),
+            0x29 => format!("LD F, V{:X}", x),
+            0x30 => format!("LD HF, V{:X}", x),
+            0x33 => format!("LD B, V{:X}", x),
+            0x55 => format!("LD [I], V{:X}", x),
+            0x65 => format!("LD V{:X}, [I]", x),
+            0x75 => format!("LD R, V{:X}", x),
+            0x85 => format!("LD V{:X}, R", x),
+            _ => format!("DATA 0x{:04X}", opcode),
+        },
+        _ => format!("DATA 0x{:04X}", opcode),
+    }
+}
+
+// Enveloppe un `Chip8` pour l'exécution pas-à-pas : historique des
+// instructions exécutées, points d'arrêt, et inspection en lecture seule
+// des registres, du PC et de la pile.
+pub struct Debugger {
+    pub chip8: Chip8,
+    history: VecDeque<(u16, u16)>,
+    pub paused: bool,
+    breakpoint: Option<u16>,
+}
+
+impl Debugger {
+    pub fn new(chip8: Chip8) -> Self {
+        Debugger {
+            chip8,
+            history: VecDeque::with_capacity(HISTORY_SIZE),
+            paused: false,
+            breakpoint: None,
+        }
+    }
+
+    // Exécute une seule instruction, même en pause (appelé par le contrôle
+    // "step" du débogueur), et l'ajoute à l'historique.
+    pub fn step(&mut self) {
+        let (pc, opcode) = self.chip8.step();
+        if self.history.len() == HISTORY_SIZE {
+            self.history.pop_front();
+        }
+        self.history.push_back((pc, opcode));
+    }
+
+    // Exécute une seule instruction (sans effet si le débogueur est en pause)
+    // et met le débogueur en pause si le PC atteint alors le point d'arrêt ;
+    // appelé en boucle, cela exécute jusqu'au point d'arrêt.
+    pub fn run(&mut self) {
+        if self.paused {
+            return;
+        }
+        self.step();
+        if Some(self.chip8.pc()) == self.breakpoint {
+            self.paused = true;
+        }
+    }
+
+    pub fn set_breakpoint(&mut self, pc: u16) {
+        self.breakpoint = Some(pc);
+    }
+
+    pub fn clear_breakpoint(&mut self) {
+        self.breakpoint = None;
+    }
+
+    // Derniers (pc, opcode) exécutés, du plus ancien au plus récent.
+    pub fn history(&self) -> impl Iterator<Item = &(u16, u16)> {
+        self.history.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disassembles_basic_opcodes() {
+        assert_eq!(disassemble(0x00E0), "CLS");
+        assert_eq!(disassemble(0x00EE), "RET");
+        assert_eq!(disassemble(0x1234), "JP 0x234");
+        assert_eq!(disassemble(0xA2F0), "LD I, 0x2F0");
+        assert_eq!(disassemble(0x6A12), "LD VA, 0x12");
+        assert_eq!(disassemble(0xD125), "DRW V1, V2, 5");
+    }
+
+    #[test]
+    fn disassembles_schip_opcodes() {
+        assert_eq!(disassemble(0x00FB), "SCR");
+        assert_eq!(disassemble(0x00FC), "SCL");
+        assert_eq!(disassemble(0x00FD), "EXIT");
+        assert_eq!(disassemble(0x00FE), "LOW");
+        assert_eq!(disassemble(0x00FF), "HIGH");
+        assert_eq!(disassemble(0x00C3), "SCD 0x3");
+        assert_eq!(disassemble(0xF130), "LD HF, V1");
+        assert_eq!(disassemble(0xF275), "LD R, V2");
+        assert_eq!(disassemble(0xF385), "LD V3, R");
+    }
+}
@@ -1,19 +1,64 @@
+use sdl2::audio::{AudioCallback, AudioSpecDesired};
 use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
 use sdl2::pixels::PixelFormatEnum;
 use std::env;
+use std::f32::consts::PI;
 use std::process;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::thread;
 
-// On importe le module qu'on a créé à l'étape précédente
+// On importe les modules qu'on a créés aux étapes précédentes
 mod chip8;
-use chip8::Chip8;
+mod debugger;
+use chip8::{Chip8, Quirks};
+use debugger::{disassemble, Debugger};
 
 const SCREEN_WIDTH: u32 = 1024;
 const SCREEN_HEIGHT: u32 = 512;
-const CHIP8_WIDTH: usize = 64;
-const CHIP8_HEIGHT: usize = 32;
+
+// Horloge de frame fixe à 60 Hz, indépendante de la vitesse d'exécution des
+// opcodes (voir `Chip8::cycles_per_frame` et `Chip8::tick_timers`).
+const FRAME_BUDGET: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+// Fichier de quick-save (F5/F9, voir Chip8::snapshot/restore).
+const SAVESTATE_PATH: &str = "savestate.chip8";
+
+// Réglages du bip sonore (opcode FX18 / sound_timer). La forme d'onde se
+// choisit via --waveform= (voir `parse_waveform`) ; fréquence et volume
+// restent fixes, comme le reste du rendu.
+const BEEP_FREQUENCY_HZ: f32 = 440.0;
+const BEEP_VOLUME: f32 = 0.25;
+
+#[derive(Clone, Copy)]
+enum Waveform {
+    Square,
+    Sine,
+}
+
+// Générateur de tonalité pour le device audio SDL2 : produit une onde
+// carrée ou sinusoïdale continue, que `main` met en pause/reprend selon
+// `Chip8::is_beeping`.
+struct ToneGenerator {
+    phase: f32,
+    phase_step: f32,
+    volume: f32,
+    waveform: Waveform,
+}
+
+impl AudioCallback for ToneGenerator {
+    type Channel = f32;
+
+    fn callback(&mut self, out: &mut [f32]) {
+        for sample in out.iter_mut() {
+            *sample = match self.waveform {
+                Waveform::Square => if self.phase < 0.5 { self.volume } else { -self.volume },
+                Waveform::Sine => (self.phase * 2.0 * PI).sin() * self.volume,
+            };
+            self.phase = (self.phase + self.phase_step) % 1.0;
+        }
+    }
+}
 
 // Mapping des touches (Clavier moderne -> Hex Keypad Chip8)
 // C'est l'équivalent de ton tableau 'keymap' en C++, mais on utilise une fonction
@@ -40,19 +85,67 @@ fn key2btn(key: Keycode) -> Option<usize> {
     }
 }
 
+// Choisit le préréglage de `Quirks` depuis les flags de la ligne de commande
+// (`--quirks=vip|schip|default`), pour que la fonctionnalité soit réellement
+// accessible depuis le binaire et pas seulement via l'API.
+fn parse_quirks(flags: &[String]) -> Quirks {
+    match flags.iter().find(|a| a.starts_with("--quirks=")).map(|s| s.as_str()) {
+        Some("--quirks=vip") => Quirks::cosmac_vip(),
+        Some("--quirks=schip") => Quirks::schip(),
+        Some("--quirks=default") | None => Quirks::default(),
+        Some(other) => {
+            eprintln!("Unknown quirks preset '{}', falling back to --quirks=default", other);
+            Quirks::default()
+        }
+    }
+}
+
+// Choisit la forme d'onde du bip depuis les flags de la ligne de commande
+// (`--waveform=square|sine`), pour que `Waveform::Sine` soit réellement
+// accessible depuis le binaire et pas seulement via l'API.
+fn parse_waveform(flags: &[String]) -> Waveform {
+    match flags.iter().find(|a| a.starts_with("--waveform=")).map(|s| s.as_str()) {
+        Some("--waveform=square") | None => Waveform::Square,
+        Some("--waveform=sine") => Waveform::Sine,
+        Some(other) => {
+            eprintln!("Unknown waveform '{}', falling back to --waveform=square", other);
+            Waveform::Square
+        }
+    }
+}
+
 fn main() {
     // 1. Gestion des arguments
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        println!("Usage: cargo run <ROM file>");
+    if args.len() < 2 {
+        println!("Usage: cargo run <ROM file> [--quirks=vip|schip|default] [--waveform=square|sine]");
         process::exit(1);
     }
     let rom_path = &args[1];
+    let flags = &args[2..];
+    let quirks = parse_quirks(flags);
+    let waveform = parse_waveform(flags);
 
     // 2. Initialisation de SDL2
     // En Rust, SDL est découpé en sous-systèmes pour gérer l'ownership.
     let sdl_context = sdl2::init().unwrap();
     let video_subsystem = sdl_context.video().unwrap();
+    let audio_subsystem = sdl_context.audio().unwrap();
+
+    let audio_spec = AudioSpecDesired {
+        freq: Some(44_100),
+        channels: Some(1),
+        samples: None,
+    };
+    let audio_device = audio_subsystem
+        .open_playback(None, &audio_spec, |spec| ToneGenerator {
+            phase: 0.0,
+            phase_step: BEEP_FREQUENCY_HZ / spec.freq as f32,
+            volume: BEEP_VOLUME,
+            waveform,
+        })
+        .unwrap();
+    let mut beeping = false;
 
     let window = video_subsystem
         .window("CHIP-8 Emulator (Rust)", SCREEN_WIDTH, SCREEN_HEIGHT)
@@ -61,27 +154,60 @@ fn main() {
         .unwrap();
 
     let mut canvas = window.into_canvas().build().unwrap();
-    
+
     let texture_creator = canvas.texture_creator();
+
+    // 3. Initialisation du Chip8, enveloppé dans un Debugger (pas-à-pas, F2)
+    let mut chip8 = Chip8::new_with_quirks(quirks);
+
+    // La texture est recréée à la résolution courante du plan graphique :
+    // en SCHIP, le ROM peut basculer entre 64x32 et 128x64 en plein jeu.
+    let mut texture_dims = (chip8.width() as u32, chip8.height() as u32);
     let mut texture = texture_creator
-        .create_texture_streaming(PixelFormatEnum::ARGB8888, CHIP8_WIDTH as u32, CHIP8_HEIGHT as u32)
+        .create_texture_streaming(PixelFormatEnum::ARGB8888, texture_dims.0, texture_dims.1)
         .unwrap();
 
-    // 3. Initialisation du Chip8
-    let mut chip8 = Chip8::new();
-    
     // Gestion propre de l'erreur de chargement (Result)
     if let Err(e) = chip8.load(rom_path) {
         eprintln!("Erreur lors du chargement de la ROM: {}", e);
         process::exit(2);
     }
 
+    let mut debugger = Debugger::new(chip8);
+
     let mut event_pump = sdl_context.event_pump().unwrap();
 
-    // 4. Boucle principale (Game Loop)
+    // 4. Boucle principale (Game Loop), cadencée à 60 Hz
     'gameloop: loop {
+        let frame_start = Instant::now();
+
         // --- Émulation ---
-        chip8.emulate_cycle();
+        // Plusieurs cycles CPU par frame : la vitesse du ROM ne dépend plus que
+        // de `cycles_per_frame` x 60 Hz, et non du temps passé dans la boucle.
+        // En pause (débogueur, F2), `run` est un no-op et seul F3 avance.
+        for _ in 0..debugger.chip8.cycles_per_frame {
+            debugger.run();
+            if debugger.chip8.should_exit {
+                break 'gameloop;
+            }
+        }
+
+        // --- Timers ---
+        // Décrémentés une seule fois par frame, donc toujours à 60 Hz quel
+        // que soit `cycles_per_frame`.
+        debugger.chip8.tick_timers();
+
+        // --- Son ---
+        // Le device audio joue en continu ; on ne fait que resume/pause
+        // pour rester synchronisé avec le tick des timers à 60 Hz.
+        if debugger.chip8.is_beeping() != beeping {
+            beeping = debugger.chip8.is_beeping();
+            if beeping {
+                audio_device.resume();
+            } else {
+                audio_device.pause();
+            }
+        }
 
         // --- Gestion des événements (Input) ---
         for event in event_pump.poll_iter() {
@@ -93,20 +219,74 @@ fn main() {
                 // Remplacement du "goto load" par une réinitialisation propre
                 Event::KeyDown { keycode: Some(Keycode::F1), .. } => {
                     println!("F1 pressed: Resetting game...");
-                    chip8 = Chip8::new();
-                    if let Err(e) = chip8.load(rom_path) {
+                    debugger.chip8 = Chip8::new_with_quirks(quirks);
+                    if let Err(e) = debugger.chip8.load(rom_path) {
                          eprintln!("Erreur critique au rechargement: {}", e);
                          break 'gameloop;
                     }
                 },
+                // F2 bascule le mode pas-à-pas ; F3 exécute une instruction
+                // tant que le débogueur est en pause.
+                Event::KeyDown { keycode: Some(Keycode::F2), .. } => {
+                    debugger.paused = !debugger.paused;
+                    println!("Debugger {}", if debugger.paused { "paused" } else { "running" });
+                },
+                Event::KeyDown { keycode: Some(Keycode::F3), .. } => {
+                    if debugger.paused {
+                        debugger.step();
+                        let (pc, opcode) = *debugger.history().last().unwrap();
+                        let sp = debugger.chip8.stack_pointer();
+                        println!(
+                            "0x{:03X}: 0x{:04X}  {}  V={:02X?} I=0x{:03X} SP={} stack={:03X?} DT={} ST={}",
+                            pc,
+                            opcode,
+                            disassemble(opcode),
+                            debugger.chip8.registers(),
+                            debugger.chip8.index_register(),
+                            sp,
+                            &debugger.chip8.stack()[..sp as usize],
+                            debugger.chip8.delay_timer,
+                            debugger.chip8.sound_timer,
+                        );
+                    }
+                },
+                // F4 : pose un point d'arrêt sur le PC courant et relance
+                // l'exécution ; F6 l'enlève (contrôles "run-until-breakpoint").
+                Event::KeyDown { keycode: Some(Keycode::F4), .. } => {
+                    let pc = debugger.chip8.pc();
+                    debugger.set_breakpoint(pc);
+                    debugger.paused = false;
+                    println!("Breakpoint set at 0x{:03X}", pc);
+                },
+                Event::KeyDown { keycode: Some(Keycode::F6), .. } => {
+                    debugger.clear_breakpoint();
+                    println!("Breakpoint cleared");
+                },
+                // F5 : quick-save ; F9 : quick-load (voir Chip8::snapshot/restore)
+                Event::KeyDown { keycode: Some(Keycode::F5), .. } => {
+                    if let Err(e) = debugger.chip8.snapshot().write_to_file(SAVESTATE_PATH) {
+                        eprintln!("Erreur lors de la sauvegarde: {}", e);
+                    } else {
+                        println!("State saved to {}", SAVESTATE_PATH);
+                    }
+                },
+                Event::KeyDown { keycode: Some(Keycode::F9), .. } => {
+                    match chip8::Chip8State::read_from_file(SAVESTATE_PATH) {
+                        Ok(state) => {
+                            debugger.chip8.restore(&state);
+                            println!("State loaded from {}", SAVESTATE_PATH);
+                        },
+                        Err(e) => eprintln!("Erreur lors du chargement: {}", e),
+                    }
+                },
                 Event::KeyDown { keycode: Some(key), .. } => {
                     if let Some(i) = key2btn(key) {
-                        chip8.key[i] = 1;
+                        debugger.chip8.key[i] = 1;
                     }
                 },
                 Event::KeyUp { keycode: Some(key), .. } => {
                     if let Some(i) = key2btn(key) {
-                        chip8.key[i] = 0;
+                        debugger.chip8.key[i] = 0;
                     }
                 },
                 _ => {}
@@ -114,20 +294,32 @@ fn main() {
         }
 
         // --- Rendu Graphique ---
-        if chip8.draw_flag {
-            chip8.draw_flag = false;
+        if debugger.chip8.draw_flag {
+            debugger.chip8.draw_flag = false;
+
+            let width = debugger.chip8.width();
+            let height = debugger.chip8.height();
+
+            // Le ROM a pu basculer de résolution (00FF/00FE) : on recrée la
+            // texture si ses dimensions ne correspondent plus au plan graphique.
+            if texture_dims != (width as u32, height as u32) {
+                texture_dims = (width as u32, height as u32);
+                texture = texture_creator
+                    .create_texture_streaming(PixelFormatEnum::ARGB8888, texture_dims.0, texture_dims.1)
+                    .unwrap();
+            }
 
             // Conversion du buffer monochrome (1 bit) en pixels ARGB (32 bits)
             // On utilise un vecteur temporaire.
             // Le format ARGB8888 demande 4 octets par pixel : B, G, R, A (selon l'endianness)
             // C++ faisait : (0x00FFFFFF * pixel) | 0xFF000000
-            
+
             texture.with_lock(None, |buffer: &mut [u8], pitch: usize| {
-                for y in 0..CHIP8_HEIGHT {
-                    for x in 0..CHIP8_WIDTH {
+                for y in 0..height {
+                    for x in 0..width {
                         let offset = y * pitch + x * 4;
-                        let pixel = chip8.gfx[y * 64 + x];
-                        
+                        let pixel = debugger.chip8.gfx[y * width + x];
+
                         // Couleur : Blanc (255, 255, 255) ou Noir (0, 0, 0)
                         let color_val = if pixel != 0 { 255 } else { 0 };
 
@@ -145,7 +337,11 @@ fn main() {
         }
 
         // --- Temporisation ---
-        // Remplace std::this_thread::sleep_for
-        thread::sleep(Duration::from_micros(1200));
+        // On dort le temps restant du budget de frame (~16.67 ms), calculé
+        // depuis `frame_start`, plutôt qu'une pause fixe par instruction.
+        let elapsed = frame_start.elapsed();
+        if elapsed < FRAME_BUDGET {
+            thread::sleep(FRAME_BUDGET - elapsed);
+        }
     }
 }
\ No newline at end of file